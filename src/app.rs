@@ -0,0 +1,1210 @@
+// ANCHOR: imports
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{Clear, ClearType},
+};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Stylize,
+    text::Line,
+    widgets::{Block, Borders, List, ListItem},
+    DefaultTerminal, Frame,
+};
+use reqwest::blocking::{Client, Response};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::config::EndpointConfig;
+use crate::error::XpctlError;
+use crate::fuzzy::{self, Candidate};
+// ANCHOR_END: imports
+
+// ANCHOR: structs
+#[derive(Debug, Default)]
+pub struct App {
+    servers: Vec<String>,
+    resources: HashMap<String, Vec<String>>,
+    container_names: HashMap<String, String>,
+    selected_index: usize,
+    exit: bool,
+    endpoints: Vec<EndpointConfig>,
+    active_endpoint: usize,
+    session_tokens: HashMap<String, String>,
+    search: Option<SearchState>,
+    checked_servers: HashSet<String>,
+    command_prompt: Option<String>,
+    results: Option<Vec<CommandOutcome>>,
+    results_scroll: u16,
+    last_error: Option<String>,
+    file_browser: Option<FileBrowserState>,
+}
+
+/// State for the remote file browser, active while the user is navigating a
+/// connection's filesystem via the XPipe fs endpoints.
+#[derive(Debug, Default)]
+struct FileBrowserState {
+    connection_id: String,
+    server: String,
+    path: String,
+    entries: Vec<FileEntry>,
+    selected: usize,
+    transfer_prompt: Option<TransferPrompt>,
+}
+
+#[derive(Debug)]
+struct TransferPrompt {
+    kind: TransferKind,
+    input: String,
+}
+
+#[derive(Debug)]
+enum TransferKind {
+    Download,
+    Upload,
+}
+
+/// Output of running a command on a single connection, rendered side by side
+/// with the other targets in the results pane.
+#[derive(Debug)]
+struct CommandOutcome {
+    server: String,
+    success: bool,
+    exit_code: Option<i32>,
+    output: String,
+}
+
+/// State for the in-process fuzzy finder overlay, active while the user is
+/// typing a query after pressing `/`.
+#[derive(Debug, Default)]
+struct SearchState {
+    query: String,
+    candidates: Vec<Candidate>,
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+#[derive(Deserialize)]
+struct HandshakeResponse {
+    sessionToken: String,
+}
+
+#[derive(Deserialize)]
+struct ConnectionQueryResponse {
+    found: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ConnectionInfoResponse {
+    infos: Vec<ConnectionInfo>,
+}
+
+#[derive(Deserialize)]
+struct ConnectionInfo {
+    name: Vec<String>,
+    #[serde(rename = "rawData")]
+    raw_data: Option<RawData>,
+}
+
+#[derive(Deserialize)]
+struct RawData {
+    #[serde(rename = "containerName")]
+    container_name: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct ExecResponse {
+    stdout: Option<String>,
+    stderr: Option<String>,
+    #[serde(rename = "exitCode")]
+    exit_code: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct FileListResponse {
+    entries: Vec<FileEntry>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct FileEntry {
+    name: String,
+    #[serde(rename = "type")]
+    kind: FileEntryKind,
+    size: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum FileEntryKind {
+    File,
+    Directory,
+}
+// ANCHOR_END: structs
+
+/// Join a directory path and an entry name, handling the root's trailing
+/// slash so we don't double it up.
+fn join_path(dir: &str, name: &str) -> String {
+    if dir.ends_with('/') {
+        format!("{dir}{name}")
+    } else {
+        format!("{dir}/{name}")
+    }
+}
+
+/// The parent of `path`, or `/` if `path` is already the root.
+fn parent_path(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(idx) => trimmed[..idx].to_string(),
+    }
+}
+
+// ANCHOR: impl App
+impl App {
+    /// Build an `App` wired up with `endpoints` and nothing else. `main` passes
+    /// the endpoints from `config::load`; integration tests pass a mock XPipe
+    /// daemon's endpoint instead.
+    pub fn with_endpoints(endpoints: Vec<EndpointConfig>) -> Self {
+        App {
+            endpoints,
+            ..Default::default()
+        }
+    }
+
+    /// The server names currently known to the app, sorted and deduplicated.
+    pub fn servers(&self) -> &[String] {
+        &self.servers
+    }
+
+    /// Server name -> connection UUIDs, as populated by `fetch_connections`.
+    pub fn resources(&self) -> &HashMap<String, Vec<String>> {
+        &self.resources
+    }
+
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        if let Err(err) = self.connect_active_endpoint() {
+            self.last_error = Some(err.to_string());
+        }
+
+        while !self.exit {
+            terminal.draw(|frame| self.draw(frame))?;
+            self.handle_events()?;
+        }
+        Ok(())
+    }
+    /// Open the fuzzy finder overlay, seeded with the current server list.
+    fn start_search(&mut self) {
+        let candidates = self
+            .servers
+            .iter()
+            .map(|server| {
+                Candidate::new(
+                    server.clone(),
+                    self.resources
+                        .get(server)
+                        .and_then(|ids| ids.first())
+                        .cloned()
+                        .unwrap_or_default(),
+                    self.container_names.get(server).map(String::as_str),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut search = SearchState {
+            matches: (0..candidates.len()).collect(),
+            candidates,
+            ..Default::default()
+        };
+        search.selected = 0;
+        self.search = Some(search);
+    }
+
+    /// Re-rank the overlay's candidates against the current query. Called on
+    /// every keystroke while the overlay is active.
+    fn update_search_matches(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.matches = fuzzy::rank(&search.query, &search.candidates);
+            search.selected = 0;
+        }
+    }
+
+    /// Confirm the highlighted overlay candidate and open a terminal to it.
+    fn confirm_search(&mut self) {
+        let Some(search) = self.search.take() else {
+            return;
+        };
+        if let Some(&idx) = search.matches.get(search.selected) {
+            let connection_id = search.candidates[idx].connection_id.clone();
+            if connection_id.is_empty() {
+                self.last_error = Some(XpctlError::ConnectionNotFound.to_string());
+            } else if let Err(err) = self.open_terminal_session(&connection_id) {
+                self.last_error = Some(err.to_string());
+            }
+        }
+    }
+    /// Toggle the checkmark on the currently highlighted server, building up
+    /// the selection set used by [`App::run_selected_commands`].
+    fn toggle_checked(&mut self) {
+        let Some(server) = self.servers.get(self.selected_index) else {
+            return;
+        };
+        if !self.checked_servers.remove(server) {
+            self.checked_servers.insert(server.clone());
+        }
+    }
+
+    /// Open the command prompt. Runs against the checked servers, or the
+    /// highlighted server if nothing is checked.
+    fn start_command_prompt(&mut self) {
+        self.command_prompt = Some(String::new());
+    }
+
+    /// Execute the pending command against every selected target and collect
+    /// the results into the results pane.
+    fn run_selected_commands(&mut self) {
+        let Some(cmd) = self.command_prompt.take() else {
+            return;
+        };
+        if cmd.is_empty() {
+            return;
+        }
+
+        let targets: Vec<String> = if self.checked_servers.is_empty() {
+            self.servers
+                .get(self.selected_index)
+                .cloned()
+                .into_iter()
+                .collect()
+        } else {
+            let mut targets: Vec<String> = self.checked_servers.iter().cloned().collect();
+            targets.sort();
+            targets
+        };
+
+        let mut outcomes = Vec::new();
+        for server in targets {
+            let Some(connection_id) = self
+                .resources
+                .get(&server)
+                .and_then(|ids| ids.first())
+                .cloned()
+            else {
+                continue;
+            };
+
+            let outcome = match self.run_command(&connection_id, &cmd) {
+                Ok(response) => CommandOutcome {
+                    server,
+                    success: response.exit_code.unwrap_or(0) == 0,
+                    exit_code: response.exit_code,
+                    output: format!(
+                        "{}{}",
+                        response.stdout.unwrap_or_default(),
+                        response.stderr.unwrap_or_default()
+                    ),
+                },
+                Err(err) => CommandOutcome {
+                    server,
+                    success: false,
+                    exit_code: None,
+                    output: format!("Request failed: {}", err),
+                },
+            };
+            outcomes.push(outcome);
+        }
+
+        self.checked_servers.clear();
+        self.results = Some(outcomes);
+        self.results_scroll = 0;
+    }
+
+    /// Open the remote file browser on the highlighted server's root.
+    fn start_file_browser(&mut self) {
+        let Some(server) = self.servers.get(self.selected_index).cloned() else {
+            return;
+        };
+        let Some(connection_id) = self
+            .resources
+            .get(&server)
+            .and_then(|ids| ids.first())
+            .cloned()
+        else {
+            return;
+        };
+
+        match self.fetch_directory(&connection_id, "/") {
+            Ok(entries) => {
+                self.file_browser = Some(FileBrowserState {
+                    connection_id,
+                    server,
+                    path: "/".to_string(),
+                    entries,
+                    selected: 0,
+                    transfer_prompt: None,
+                });
+            }
+            Err(err) => self.last_error = Some(err.to_string()),
+        }
+    }
+
+    /// Re-list `path` on the browser's connection and move into it.
+    fn file_browser_navigate(&mut self, path: String) {
+        let Some(connection_id) = self.file_browser.as_ref().map(|b| b.connection_id.clone())
+        else {
+            return;
+        };
+
+        match self.fetch_directory(&connection_id, &path) {
+            Ok(entries) => {
+                if let Some(browser) = &mut self.file_browser {
+                    browser.path = path;
+                    browser.entries = entries;
+                    browser.selected = 0;
+                }
+            }
+            Err(err) => self.last_error = Some(err.to_string()),
+        }
+    }
+
+    fn handle_file_browser_event(&mut self, key_event: KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+
+        if self
+            .file_browser
+            .as_ref()
+            .and_then(|b| b.transfer_prompt.as_ref())
+            .is_some()
+        {
+            self.handle_transfer_prompt_event(key_event);
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Esc => self.file_browser = None,
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(browser) = &mut self.file_browser {
+                    if browser.selected + 1 < browser.entries.len() {
+                        browser.selected += 1;
+                    }
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(browser) = &mut self.file_browser {
+                    browser.selected = browser.selected.saturating_sub(1);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(browser) = &self.file_browser {
+                    let parent = parent_path(&browser.path);
+                    self.file_browser_navigate(parent);
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(browser) = &self.file_browser {
+                    if let Some(entry) = browser.entries.get(browser.selected) {
+                        if entry.kind == FileEntryKind::Directory {
+                            let path = join_path(&browser.path, &entry.name);
+                            self.file_browser_navigate(path);
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('d') => {
+                let is_file = self
+                    .file_browser
+                    .as_ref()
+                    .and_then(|b| b.entries.get(b.selected))
+                    .is_some_and(|entry| entry.kind == FileEntryKind::File);
+                if is_file {
+                    if let Some(browser) = &mut self.file_browser {
+                        browser.transfer_prompt = Some(TransferPrompt {
+                            kind: TransferKind::Download,
+                            input: String::new(),
+                        });
+                    }
+                }
+            }
+            KeyCode::Char('u') => {
+                if let Some(browser) = &mut self.file_browser {
+                    browser.transfer_prompt = Some(TransferPrompt {
+                        kind: TransferKind::Upload,
+                        input: String::new(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_transfer_prompt_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc => {
+                if let Some(browser) = &mut self.file_browser {
+                    browser.transfer_prompt = None;
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(prompt) = self.active_transfer_prompt() {
+                    prompt.input.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(prompt) = self.active_transfer_prompt() {
+                    prompt.input.push(c);
+                }
+            }
+            KeyCode::Enter => self.run_transfer(),
+            _ => {}
+        }
+    }
+
+    fn active_transfer_prompt(&mut self) -> Option<&mut TransferPrompt> {
+        self.file_browser
+            .as_mut()
+            .and_then(|browser| browser.transfer_prompt.as_mut())
+    }
+
+    /// Execute the pending download/upload against the highlighted entry (or
+    /// the current directory, for uploads) and report the outcome.
+    fn run_transfer(&mut self) {
+        let Some(browser) = self.file_browser.take() else {
+            return;
+        };
+        let Some(prompt) = &browser.transfer_prompt else {
+            self.file_browser = Some(browser);
+            return;
+        };
+        let local_path = prompt.input.clone();
+
+        let result = match prompt.kind {
+            TransferKind::Download => browser
+                .entries
+                .get(browser.selected)
+                .map(|entry| join_path(&browser.path, &entry.name))
+                .ok_or(XpctlError::ConnectionNotFound)
+                .and_then(|remote_path| {
+                    self.download_file(&browser.connection_id, &remote_path, &local_path)
+                }),
+            TransferKind::Upload => {
+                let remote_path = join_path(
+                    &browser.path,
+                    std::path::Path::new(&local_path)
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("upload.bin"),
+                );
+                self.upload_file(&browser.connection_id, &remote_path, &local_path)
+            }
+        };
+
+        let mut browser = browser;
+        browser.transfer_prompt = None;
+        if let Err(err) = result {
+            self.last_error = Some(err.to_string());
+        }
+
+        let path = browser.path.clone();
+        self.file_browser = Some(browser);
+        self.file_browser_navigate(path);
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        if let Some(results) = &self.results {
+            self.draw_results(frame, results);
+            return;
+        }
+
+        if let Some(browser) = &self.file_browser {
+            self.draw_file_browser(frame, browser);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .servers
+            .iter()
+            .enumerate()
+            .map(|(i, server)| {
+                let checkbox = if self.checked_servers.contains(server) {
+                    "[x] "
+                } else {
+                    "[ ] "
+                };
+                let text = format!("{checkbox}{server}");
+                let content = if i == self.selected_index {
+                    Line::from(text.bold().yellow())
+                } else {
+                    Line::from(text)
+                };
+                ListItem::new(content)
+            })
+            .collect();
+
+        let block = Block::default()
+            .title(format!("SSH Clients — {}", self.active_endpoint().name))
+            .borders(Borders::ALL);
+        let list = List::new(items).block(block);
+
+        frame.render_widget(list, frame.area());
+
+        // Display navigation instructions
+        let instructions = Line::from(
+            "Navigation: ↑/↓ or j/k to move | Enter to select | Tab to switch endpoint | q to quit"
+                .bold()
+                .cyan(),
+        );
+
+        let area = frame.area();
+        let instruction_area = ratatui::layout::Rect {
+            x: area.x,
+            y: area.y + area.height.saturating_sub(1),
+            width: area.width,
+            height: 1,
+        };
+
+        frame.render_widget(instructions, instruction_area);
+
+        if let Some(err) = &self.last_error {
+            let error_area = ratatui::layout::Rect {
+                x: area.x,
+                y: area.y + area.height.saturating_sub(2),
+                width: area.width,
+                height: 1,
+            };
+            frame.render_widget(Line::from(err.clone().red()), error_area);
+        }
+
+        if let Some(search) = &self.search {
+            self.draw_search_overlay(frame, search);
+        }
+
+        if let Some(cmd) = &self.command_prompt {
+            self.draw_command_prompt(frame, cmd);
+        }
+    }
+
+    fn draw_command_prompt(&self, frame: &mut Frame, cmd: &str) {
+        let area = frame.area();
+        let prompt_area = ratatui::layout::Rect {
+            x: area.x,
+            y: area.y + area.height.saturating_sub(4),
+            width: area.width,
+            height: 3,
+        };
+        let targets = if self.checked_servers.is_empty() {
+            1
+        } else {
+            self.checked_servers.len()
+        };
+        let block = Block::default()
+            .title(format!(
+                "Run command on {targets} host(s) — Enter to run, Esc to cancel"
+            ))
+            .borders(Borders::ALL);
+        frame.render_widget(
+            ratatui::widgets::Paragraph::new(Line::from(format!("$ {cmd}"))).block(block),
+            prompt_area,
+        );
+    }
+
+    fn draw_results(&self, frame: &mut Frame, results: &[CommandOutcome]) {
+        let mut lines = Vec::new();
+        for outcome in results {
+            let status = match outcome.exit_code {
+                Some(code) => format!("exit {code}"),
+                None => "error".to_string(),
+            };
+            let header = format!("== {} ({status}) ==", outcome.server);
+            lines.push(if outcome.success {
+                Line::from(header.green())
+            } else {
+                Line::from(header.red())
+            });
+            for line in outcome.output.lines() {
+                lines.push(Line::from(line.to_string()));
+            }
+        }
+
+        let block = Block::default()
+            .title("Command results (j/k or PageUp/PageDown to scroll, any other key to return)")
+            .borders(Borders::ALL);
+        frame.render_widget(
+            ratatui::widgets::Paragraph::new(lines)
+                .block(block)
+                .scroll((self.results_scroll, 0)),
+            frame.area(),
+        );
+    }
+
+    fn draw_file_browser(&self, frame: &mut Frame, browser: &FileBrowserState) {
+        let items: Vec<ListItem> = browser
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let suffix = match entry.kind {
+                    FileEntryKind::Directory => "/".to_string(),
+                    FileEntryKind::File => entry
+                        .size
+                        .map(|size| format!(" ({size}b)"))
+                        .unwrap_or_default(),
+                };
+                let text = format!("{}{suffix}", entry.name);
+                let content = if i == browser.selected {
+                    Line::from(text.bold().yellow())
+                } else {
+                    Line::from(text)
+                };
+                ListItem::new(content)
+            })
+            .collect();
+
+        let block = Block::default()
+            .title(format!(
+                "{}:{} — Enter to open, Backspace up, d download, u upload, Esc to close",
+                browser.server, browser.path
+            ))
+            .borders(Borders::ALL);
+        frame.render_widget(List::new(items).block(block), frame.area());
+
+        if let Some(prompt) = &browser.transfer_prompt {
+            let area = frame.area();
+            let prompt_area = ratatui::layout::Rect {
+                x: area.x,
+                y: area.y + area.height.saturating_sub(4),
+                width: area.width,
+                height: 3,
+            };
+            let title = match prompt.kind {
+                TransferKind::Download => {
+                    "Download to local path (Enter to confirm, Esc to cancel)"
+                }
+                TransferKind::Upload => "Upload local path (Enter to confirm, Esc to cancel)",
+            };
+            let prompt_block = Block::default().title(title).borders(Borders::ALL);
+            frame.render_widget(
+                ratatui::widgets::Paragraph::new(Line::from(format!("$ {}", prompt.input)))
+                    .block(prompt_block),
+                prompt_area,
+            );
+        }
+    }
+
+    fn draw_search_overlay(&self, frame: &mut Frame, search: &SearchState) {
+        let area = frame.area();
+        let [input_area, list_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+
+        let input_block = Block::default().title("Search").borders(Borders::ALL);
+        let input_line = Line::from(format!("/{}", search.query));
+        frame.render_widget(
+            ratatui::widgets::Paragraph::new(input_line).block(input_block),
+            input_area,
+        );
+
+        let items: Vec<ListItem> = search
+            .matches
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| {
+                let label = &search.candidates[idx].label;
+                let content = if i == search.selected {
+                    Line::from(label.clone().bold().yellow())
+                } else {
+                    Line::from(label.clone())
+                };
+                ListItem::new(content)
+            })
+            .collect();
+
+        let results_block = Block::default()
+            .title("Matches (Enter to connect, Esc to cancel)")
+            .borders(Borders::ALL);
+        frame.render_widget(List::new(items).block(results_block), list_area);
+    }
+
+    fn handle_events(&mut self) -> io::Result<()> {
+        match event::read()? {
+            Event::Key(key_event) if self.results.is_some() => {
+                self.handle_results_event(key_event);
+            }
+            Event::Key(key_event) if self.command_prompt.is_some() => {
+                self.handle_command_prompt_event(key_event);
+            }
+            Event::Key(key_event) if self.search.is_some() => {
+                self.handle_search_event(key_event);
+            }
+            Event::Key(key_event) if self.file_browser.is_some() => {
+                self.handle_file_browser_event(key_event);
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('f'),
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.start_file_browser();
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(' '),
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.toggle_checked();
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.start_command_prompt();
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Tab,
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.cycle_endpoint();
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Down | KeyCode::Char('j'),
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                if self.selected_index + 1 < self.servers.len() {
+                    self.selected_index += 1;
+                }
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Up | KeyCode::Char('k'),
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                if self.selected_index > 0 {
+                    self.selected_index -= 1;
+                }
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                let Some(selected_server) = self.servers.get(self.selected_index).cloned() else {
+                    self.last_error = Some(XpctlError::ConnectionNotFound.to_string());
+                    return Ok(());
+                };
+                if let Some(connection_id) = self
+                    .resources
+                    .get(&selected_server)
+                    .and_then(|ids| ids.first())
+                    .cloned()
+                {
+                    if let Err(err) = self.open_terminal_session(&connection_id) {
+                        self.last_error = Some(err.to_string());
+                    }
+                } else {
+                    self.last_error = Some(XpctlError::ConnectionNotFound.to_string());
+                }
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('/'),
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.start_search();
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('q'),
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                self.exit = true;
+            }
+            _ => {}
+        };
+        Ok(())
+    }
+
+    fn handle_search_event(&mut self, key_event: KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+        match key_event.code {
+            KeyCode::Esc => {
+                self.search = None;
+                self.last_error = Some(XpctlError::Cancelled.to_string());
+            }
+            KeyCode::Enter => self.confirm_search(),
+            KeyCode::Down => {
+                if let Some(search) = &mut self.search {
+                    if search.selected + 1 < search.matches.len() {
+                        search.selected += 1;
+                    }
+                }
+            }
+            KeyCode::Up => {
+                if let Some(search) = &mut self.search {
+                    search.selected = search.selected.saturating_sub(1);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(search) = &mut self.search {
+                    search.query.pop();
+                }
+                self.update_search_matches();
+            }
+            KeyCode::Char(c) => {
+                if let Some(search) = &mut self.search {
+                    search.query.push(c);
+                }
+                self.update_search_matches();
+            }
+            _ => {}
+        }
+    }
+
+    /// Scroll the results pane on j/k or PageUp/PageDown; any other key
+    /// dismisses it back to the server list.
+    fn handle_results_event(&mut self, key_event: KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+        match key_event.code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.results_scroll = self.results_scroll.saturating_add(1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.results_scroll = self.results_scroll.saturating_sub(1);
+            }
+            KeyCode::PageDown => {
+                self.results_scroll = self.results_scroll.saturating_add(10);
+            }
+            KeyCode::PageUp => {
+                self.results_scroll = self.results_scroll.saturating_sub(10);
+            }
+            _ => {
+                self.results = None;
+                self.results_scroll = 0;
+            }
+        }
+    }
+
+    fn handle_command_prompt_event(&mut self, key_event: KeyEvent) {
+        if key_event.kind != KeyEventKind::Press {
+            return;
+        }
+        match key_event.code {
+            KeyCode::Esc => {
+                self.command_prompt = None;
+                self.last_error = Some(XpctlError::Cancelled.to_string());
+            }
+            KeyCode::Enter => self.run_selected_commands(),
+            KeyCode::Backspace => {
+                if let Some(cmd) = &mut self.command_prompt {
+                    cmd.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(cmd) = &mut self.command_prompt {
+                    cmd.push(c);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The endpoint the TUI is currently browsing.
+    fn active_endpoint(&self) -> &EndpointConfig {
+        &self.endpoints[self.active_endpoint]
+    }
+
+    fn http_client(endpoint: &EndpointConfig) -> Client {
+        Client::builder()
+            .danger_accept_invalid_certs(endpoint.insecure_tls)
+            .build()
+            .unwrap_or_else(|_| Client::new())
+    }
+
+    /// Handshake with, then fetch connections from, the active endpoint,
+    /// reusing its cached session token if we already have one.
+    pub fn connect_active_endpoint(&mut self) -> Result<(), XpctlError> {
+        let name = self.active_endpoint().name.clone();
+        if !self.session_tokens.contains_key(&name) {
+            let token = self.handshake()?;
+            self.session_tokens.insert(name, token);
+        }
+        self.fetch_connections()
+    }
+
+    /// Cycle to the next configured endpoint and refresh the server list for
+    /// it, clearing out the previous endpoint's connections.
+    fn cycle_endpoint(&mut self) {
+        if self.endpoints.len() < 2 {
+            return;
+        }
+        self.active_endpoint = (self.active_endpoint + 1) % self.endpoints.len();
+        self.servers.clear();
+        self.resources.clear();
+        self.container_names.clear();
+        self.selected_index = 0;
+        if let Err(err) = self.connect_active_endpoint() {
+            self.last_error = Some(err.to_string());
+        }
+    }
+
+    pub fn handshake(&self) -> Result<String, XpctlError> {
+        let endpoint = self.active_endpoint();
+        let api_key = endpoint.api_key.clone().ok_or(XpctlError::AuthDenied)?;
+        let client = Self::http_client(endpoint);
+
+        let response: HandshakeResponse = client
+            .post(format!("{}/handshake", endpoint.base_url))
+            .json(&json!({
+                "auth": {
+                    "type": "ApiKey",
+                    "key": api_key
+                },
+                "client": {
+                    "type": "Api",
+                    "name": "xpcli"
+                }
+            }))
+            .send()?
+            .json()?;
+
+        Ok(response.sessionToken)
+    }
+
+    /// Issue a POST to `path` on the active endpoint, letting `build` attach
+    /// the body to the bearer-authed request builder. Transparently
+    /// re-handshakes once on a `401`/`403`, maps a `404` to
+    /// `ConnectionNotFound`, and retries any other non-success status up to
+    /// `MAX_RETRIES` times with exponential backoff; a transport error is
+    /// returned at once without a retry. Shared by [`App::authed_post`] and
+    /// [`App::authed_post_bytes`] so the token-lifecycle handling lives in
+    /// one place.
+    fn authed_request<F>(&mut self, path: &str, build: F) -> Result<Response, XpctlError>
+    where
+        F: Fn(reqwest::blocking::RequestBuilder) -> reqwest::blocking::RequestBuilder,
+    {
+        const MAX_RETRIES: u32 = 3;
+        const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+
+        let mut attempt = 0;
+        let mut rehandshaked = false;
+
+        loop {
+            let endpoint = self.active_endpoint().clone();
+            let client = Self::http_client(&endpoint);
+            let token = self
+                .session_tokens
+                .get(&endpoint.name)
+                .cloned()
+                .ok_or(XpctlError::AuthDenied)?;
+
+            let request = build(
+                client
+                    .post(format!("{}{}", endpoint.base_url, path))
+                    .bearer_auth(token),
+            );
+            let response = request.send()?;
+
+            match response.status() {
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN if !rehandshaked => {
+                    rehandshaked = true;
+                    let token = self.handshake()?;
+                    self.session_tokens.insert(endpoint.name, token);
+                }
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                    return Err(XpctlError::AuthDenied);
+                }
+                StatusCode::NOT_FOUND => return Err(XpctlError::ConnectionNotFound),
+                status if status.is_success() => return Ok(response),
+                _ if attempt < MAX_RETRIES => {
+                    attempt += 1;
+                    thread::sleep(INITIAL_BACKOFF * 2u32.pow(attempt - 1));
+                }
+                _ => {
+                    return Err(XpctlError::Network(
+                        response.error_for_status().unwrap_err(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// POST `payload` as JSON to `path` on the active endpoint. See
+    /// [`App::authed_request`] for the retry/re-handshake behavior.
+    fn authed_post(
+        &mut self,
+        path: &str,
+        payload: serde_json::Value,
+    ) -> Result<Response, XpctlError> {
+        self.authed_request(path, |req| req.json(&payload))
+    }
+
+    pub fn fetch_connections(&mut self) -> Result<(), XpctlError> {
+        let response: ConnectionQueryResponse = self
+            .authed_post(
+                "/connection/query",
+                json!({
+                    "categoryFilter": "*",
+                    "connectionFilter": "*",
+                    "typeFilter": "ssh"
+                }),
+            )?
+            .json()?;
+
+        let connection_ids = response.found;
+
+        let info_response: ConnectionInfoResponse = self
+            .authed_post(
+                "/connection/info",
+                json!({
+                    "connections": connection_ids
+                }),
+            )?
+            .json()?;
+
+        for (info, connection_id) in info_response.infos.into_iter().zip(connection_ids) {
+            if let Some(server_name) = info.name.first() {
+                self.servers.push(server_name.clone());
+                self.resources
+                    .entry(server_name.clone())
+                    .or_default()
+                    .push(connection_id); // Store the UUID directly
+                if let Some(container_name) = info
+                    .raw_data
+                    .as_ref()
+                    .and_then(|raw| raw.container_name.clone())
+                {
+                    self.container_names
+                        .insert(server_name.clone(), container_name);
+                }
+            }
+        }
+
+        self.servers.sort();
+        self.servers.dedup();
+
+        Ok(())
+    }
+
+    pub fn open_terminal_session(&mut self, connection_uuid: &str) -> Result<(), XpctlError> {
+        execute!(io::stdout(), Clear(ClearType::All)).unwrap();
+        println!("Connecting to {}...", connection_uuid);
+
+        let result = self.authed_post(
+            "/connection/terminal",
+            json!({
+                "connection": connection_uuid,
+                "directory": "/"
+            }),
+        );
+
+        match &result {
+            Ok(_) => println!(
+                "Terminal session opened successfully for: {}",
+                connection_uuid
+            ),
+            Err(err) => println!("Error opening terminal session: {err}"),
+        }
+
+        println!("Press any key to return...");
+        let _ = event::read();
+
+        result.map(|_| ())
+    }
+
+    /// Run `cmd` on `connection_uuid` non-interactively via XPipe's exec
+    /// endpoint, returning its captured output instead of opening a terminal.
+    fn run_command(
+        &mut self,
+        connection_uuid: &str,
+        cmd: &str,
+    ) -> Result<ExecResponse, XpctlError> {
+        let response = self.authed_post(
+            "/connection/exec",
+            json!({
+                "connection": connection_uuid,
+                "command": cmd,
+            }),
+        )?;
+        Ok(response.json()?)
+    }
+
+    /// List the entries of `path` on `connection_uuid` via XPipe's fs
+    /// endpoint.
+    fn fetch_directory(
+        &mut self,
+        connection_uuid: &str,
+        path: &str,
+    ) -> Result<Vec<FileEntry>, XpctlError> {
+        let response: FileListResponse = self
+            .authed_post(
+                "/connection/fs/list",
+                json!({
+                    "connection": connection_uuid,
+                    "path": path,
+                }),
+            )?
+            .json()?;
+        Ok(response.entries)
+    }
+
+    /// Download `remote_path` from `connection_uuid` to `local_path`.
+    fn download_file(
+        &mut self,
+        connection_uuid: &str,
+        remote_path: &str,
+        local_path: &str,
+    ) -> Result<(), XpctlError> {
+        let response = self.authed_post(
+            "/connection/fs/download",
+            json!({
+                "connection": connection_uuid,
+                "path": remote_path,
+            }),
+        )?;
+        let bytes = response.bytes()?;
+        std::fs::write(local_path, bytes)?;
+        Ok(())
+    }
+
+    /// Upload `local_path` to `remote_path` on `connection_uuid`.
+    fn upload_file(
+        &mut self,
+        connection_uuid: &str,
+        remote_path: &str,
+        local_path: &str,
+    ) -> Result<(), XpctlError> {
+        let bytes = std::fs::read(local_path)?;
+        self.authed_post_bytes(
+            "/connection/fs/upload",
+            &[("connection", connection_uuid), ("path", remote_path)],
+            bytes,
+        )?;
+        Ok(())
+    }
+
+    /// Like [`App::authed_post`], but sends a raw byte body instead of JSON —
+    /// used for file uploads. `query` is attached via `RequestBuilder::query`
+    /// so values are percent-encoded instead of being formatted raw into the
+    /// path.
+    fn authed_post_bytes(
+        &mut self,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> Result<Response, XpctlError> {
+        self.authed_request(path, |req| req.query(query).body(body.clone()))
+    }
+}
+// ANCHOR_END: impl App
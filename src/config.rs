@@ -0,0 +1,61 @@
+// ANCHOR: config
+//! Multi-instance configuration: named XPipe endpoints loaded from a TOML
+//! file, with environment overrides layered on top. This is what lets xpctl
+//! talk to more than one XPipe daemon instead of a hardcoded local instance.
+
+use serde::Deserialize;
+
+const DEFAULT_CONFIG_PATH: &str = "xpctl.toml";
+const DEFAULT_BASE_URL: &str = "http://localhost:21721";
+
+#[derive(Debug, Deserialize, Default)]
+struct RawConfig {
+    #[serde(default)]
+    endpoint: Vec<EndpointConfig>,
+}
+
+/// A single named XPipe daemon: its base URL, API key, and whether to accept
+/// a self-signed/otherwise unverified TLS certificate.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EndpointConfig {
+    pub name: String,
+    pub base_url: String,
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub insecure_tls: bool,
+}
+
+/// Load the configured endpoints from `XPCTL_CONFIG_PATH` (or `xpctl.toml` in
+/// the current working directory if unset). `XPIPE_API_URL`/`XPIPE_API_KEY` override the
+/// first endpoint, preserving the old single-instance env var workflow when
+/// no config file is present.
+pub fn load() -> Vec<EndpointConfig> {
+    let path =
+        std::env::var("XPCTL_CONFIG_PATH").unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string());
+
+    let mut raw: RawConfig = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default();
+
+    if raw.endpoint.is_empty() {
+        raw.endpoint.push(EndpointConfig {
+            name: "default".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: None,
+            insecure_tls: false,
+        });
+    }
+
+    if let Some(first) = raw.endpoint.first_mut() {
+        if let Ok(url) = std::env::var("XPIPE_API_URL") {
+            first.base_url = url;
+        }
+        if let Ok(key) = std::env::var("XPIPE_API_KEY") {
+            first.api_key = Some(key);
+        }
+    }
+
+    raw.endpoint
+}
+// ANCHOR_END: config
@@ -0,0 +1,50 @@
+// ANCHOR: error
+//! Error taxonomy shared by every XPipe API call, surfaced in the TUI instead
+//! of being printed to stderr and lost behind the alternate screen.
+
+use std::fmt;
+
+#[derive(Debug)]
+pub enum XpctlError {
+    /// Transport-level failure (DNS, connection refused, timeout, etc.), an
+    /// undecodable response body, or an error status that persisted through
+    /// retries.
+    Network(reqwest::Error),
+    /// The daemon rejected our session token even after a re-handshake, or
+    /// the endpoint has no API key configured.
+    AuthDenied,
+    /// The daemon answered `404`, or no connection (or remote entry) is
+    /// selected locally.
+    ConnectionNotFound,
+    /// The user backed out of a selection (search overlay, command prompt).
+    Cancelled,
+    /// A local filesystem operation failed (e.g. during file transfer).
+    Io(std::io::Error),
+}
+
+impl fmt::Display for XpctlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XpctlError::Network(err) => write!(f, "network error: {err}"),
+            XpctlError::AuthDenied => write!(f, "authentication denied by XPipe daemon"),
+            XpctlError::ConnectionNotFound => write!(f, "connection not found"),
+            XpctlError::Cancelled => write!(f, "cancelled"),
+            XpctlError::Io(err) => write!(f, "local filesystem error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for XpctlError {}
+
+impl From<reqwest::Error> for XpctlError {
+    fn from(err: reqwest::Error) -> Self {
+        XpctlError::Network(err)
+    }
+}
+
+impl From<std::io::Error> for XpctlError {
+    fn from(err: std::io::Error) -> Self {
+        XpctlError::Io(err)
+    }
+}
+// ANCHOR_END: error
@@ -0,0 +1,121 @@
+// ANCHOR: fuzzy
+//! In-process subsequence fuzzy matcher used by the `/` search overlay.
+//!
+//! This replaces shelling out to the external `fzf` binary: candidates are
+//! scored directly so search works identically on any machine, regardless of
+//! what's on `PATH`.
+
+const MATCH_SCORE: i64 = 16;
+const CONSECUTIVE_BONUS: i64 = 8;
+const WORD_BOUNDARY_BONUS: i64 = 6;
+const LEADING_GAP_PENALTY: i64 = 1;
+
+/// Score `candidate` against `query` using a greedy, case-insensitive
+/// subsequence match. Returns `None` if some character of `query` can't be
+/// found in order in `candidate`, otherwise a higher-is-better score.
+pub fn score_match(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+
+    let mut query_idx = 0;
+    let mut score: i64 = 0;
+    let mut prev_match_idx: Option<usize> = None;
+    let mut first_match_idx: Option<usize> = None;
+
+    for (idx, &ch) in candidate_chars.iter().enumerate() {
+        if query_idx >= query_chars.len() {
+            break;
+        }
+        // Lower-case per char (rather than lower-casing the whole candidate
+        // up front) so this index stays aligned with `candidate_chars` even
+        // when a char's lower-casing would otherwise expand to more than one
+        // char (e.g. 'İ').
+        let lower = ch.to_lowercase().next().unwrap_or(ch);
+        if lower != query_chars[query_idx] {
+            continue;
+        }
+
+        first_match_idx.get_or_insert(idx);
+
+        let mut bonus = MATCH_SCORE;
+        if prev_match_idx == Some(idx.wrapping_sub(1)) {
+            bonus += CONSECUTIVE_BONUS;
+        }
+        if is_word_boundary(&candidate_chars, idx) {
+            bonus += WORD_BOUNDARY_BONUS;
+        }
+
+        score += bonus;
+        prev_match_idx = Some(idx);
+        query_idx += 1;
+    }
+
+    if query_idx < query_chars.len() {
+        return None;
+    }
+
+    if let Some(first) = first_match_idx {
+        score -= first as i64 * LEADING_GAP_PENALTY;
+    }
+
+    Some(score)
+}
+
+fn is_word_boundary(chars: &[char], idx: usize) -> bool {
+    if idx == 0 {
+        return true;
+    }
+    let prev = chars[idx - 1];
+    if matches!(prev, '/' | '-' | '_' | ' ') {
+        return true;
+    }
+    chars[idx].is_uppercase() && prev.is_lowercase()
+}
+
+/// A single candidate in the search overlay: the label shown to the user and
+/// the connection UUID it resolves to.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub label: String,
+    pub connection_id: String,
+    searchable: String,
+}
+
+impl Candidate {
+    pub fn new(
+        label: impl Into<String>,
+        connection_id: impl Into<String>,
+        metadata: Option<&str>,
+    ) -> Self {
+        let label = label.into();
+        let searchable = match metadata {
+            Some(meta) if !meta.is_empty() => format!("{label} {meta}"),
+            _ => label.clone(),
+        };
+        Self {
+            label,
+            connection_id: connection_id.into(),
+            searchable,
+        }
+    }
+}
+
+/// Filter and rank `candidates` against `query`, returning indices into
+/// `candidates` sorted by descending score (stable on ties).
+pub fn rank(query: &str, candidates: &[Candidate]) -> Vec<usize> {
+    let mut scored: Vec<(usize, i64)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candidate)| {
+            score_match(query, &candidate.searchable).map(|score| (i, score))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+// ANCHOR_END: fuzzy
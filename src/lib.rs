@@ -0,0 +1,12 @@
+//! Library surface for `xpctl`, split out from the binary so the TUI's
+//! networking and response-parsing logic can be exercised by integration
+//! tests without a live XPipe daemon.
+
+pub mod app;
+pub mod config;
+pub mod error;
+pub mod fuzzy;
+
+pub use app::App;
+pub use config::EndpointConfig;
+pub use error::XpctlError;
@@ -0,0 +1,151 @@
+//! Integration tests that exercise the handshake/fetch-connections wire
+//! contract against an in-process mock XPipe daemon, instead of the real
+//! thing. Each test spins up its own `wiremock::MockServer` so they can run
+//! concurrently without sharing state.
+//!
+//! `open_terminal_session` is deliberately not covered here: it blocks on
+//! `event::read()` for a keypress once the request completes, which would
+//! hang a non-interactive test process.
+
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xpctl::{App, EndpointConfig};
+
+fn endpoint(base_url: String) -> EndpointConfig {
+    EndpointConfig {
+        name: "mock".to_string(),
+        base_url,
+        api_key: Some("test-api-key".to_string()),
+        insecure_tls: false,
+    }
+}
+
+#[tokio::test]
+async fn handshake_token_is_forwarded_to_later_requests() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/handshake"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "sessionToken": "session-abc"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/connection/query"))
+        .and(header("authorization", "Bearer session-abc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "found": ["uuid-1"]
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/connection/info"))
+        .and(header("authorization", "Bearer session-abc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "infos": [{ "name": ["box-1"] }]
+        })))
+        .mount(&server)
+        .await;
+
+    let mut app = App::with_endpoints(vec![endpoint(server.uri())]);
+    tokio::task::spawn_blocking(move || app.connect_active_endpoint())
+        .await
+        .unwrap()
+        .expect("connect_active_endpoint should succeed");
+}
+
+#[tokio::test]
+async fn fetch_connections_zips_found_ids_with_infos_and_dedups_sorts_servers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/handshake"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "sessionToken": "session-abc"
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/connection/query"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "found": ["uuid-b", "uuid-a", "uuid-c"]
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/connection/info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "infos": [
+                { "name": ["zebra"] },
+                { "name": ["apple"] },
+                { "name": ["apple"] }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let endpoints = vec![endpoint(server.uri())];
+    let app = tokio::task::spawn_blocking(move || {
+        let mut app = App::with_endpoints(endpoints);
+        app.connect_active_endpoint().expect("should succeed");
+        app
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(app.servers(), ["apple", "zebra"]);
+    assert_eq!(
+        app.resources().get("zebra").map(Vec::as_slice),
+        Some(["uuid-b"].as_slice())
+    );
+}
+
+#[tokio::test]
+async fn malformed_connection_info_does_not_panic() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/handshake"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "sessionToken": "session-abc"
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/connection/query"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "found": ["uuid-1", "uuid-2"]
+        })))
+        .mount(&server)
+        .await;
+
+    // Fewer infos than found connection ids, and one entry with no name at
+    // all — fetch_connections zips by position and should just skip what it
+    // can't resolve rather than panicking.
+    Mock::given(method("POST"))
+        .and(path("/connection/info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "infos": [{ "name": [] }]
+        })))
+        .mount(&server)
+        .await;
+
+    let endpoints = vec![endpoint(server.uri())];
+    let result = tokio::task::spawn_blocking(move || {
+        let mut app = App::with_endpoints(endpoints);
+        app.connect_active_endpoint()?;
+        Ok::<_, xpctl::XpctlError>(app)
+    })
+    .await
+    .unwrap();
+
+    let app = result.expect("malformed but well-formed JSON should not error");
+    assert!(app.servers().is_empty());
+}